@@ -119,6 +119,9 @@ pub async fn build_package(
         match task_repo.create(&parent_task).await {
             Ok(parent_id) => {
                 response_task_ids.push(parent_id); // 记录父任务ID
+                if let Err(e) = task_repo.save_build_request(parent_id, &request).await {
+                    tracing::warn!("⚠️  保存父任务 #{} 的构建请求失败: {}", parent_id, e);
+                }
                 
                 // 创建子任务（全部 Pending）
                 for arch in &request.architectures {
@@ -310,3 +310,108 @@ pub async fn get_task_log(
     }
 }
 
+
+/// 获取任务的步骤执行记录
+pub async fn task_steps(
+    State(state): State<AppState>,
+    AxumPath(task_id): AxumPath<i64>,
+) -> impl IntoResponse {
+    let task_repo = match &state.task_repo {
+        Some(repo) => repo.clone(),
+        None => {
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                AxumJson(serde_json::json!({"error": "Database not available"})),
+            ).into_response();
+        }
+    };
+    
+    match task_repo.list_steps(task_id).await {
+        Ok(steps) => (StatusCode::OK, AxumJson(serde_json::json!({"steps": steps}))).into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            AxumJson(serde_json::json!({"error": format!("Failed to get task steps: {}", e)})),
+        ).into_response(),
+    }
+}
+
+/// 恢复失败（或已取消）的任务：任务重新进入队列，执行时从第一个未成功的步骤开始
+pub async fn resume_task(
+    State(state): State<AppState>,
+    AxumPath(task_id): AxumPath<i64>,
+) -> impl IntoResponse {
+    let task_repo = match &state.task_repo {
+        Some(repo) => repo.clone(),
+        None => {
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Database not available",
+            ).into_response();
+        }
+    };
+    
+    let task = match task_repo.find_by_id(task_id).await {
+        Ok(task) => task,
+        Err(e) => {
+            return (
+                StatusCode::NOT_FOUND,
+                format!("Task not found: {}", e),
+            ).into_response();
+        }
+    };
+    
+    if !matches!(task.state, crate::model::state::TaskState::Failed | crate::model::state::TaskState::Cancelled) {
+        return (
+            StatusCode::CONFLICT,
+            format!("Task {} is {}, only failed or cancelled tasks can be resumed", task_id, task.state.as_str()),
+        ).into_response();
+    }
+    
+    // 多架构父任务本身不执行构建步骤，需要分别恢复失败的子任务
+    if task.architecture.is_none() {
+        return (
+            StatusCode::BAD_REQUEST,
+            format!("Task {} is a parent task, resume its failed child tasks instead", task_id),
+        ).into_response();
+    }
+    
+    match task_repo.get_build_request(task_id).await {
+        Ok(Some(_)) => {},
+        Ok(None) => {
+            return (
+                StatusCode::CONFLICT,
+                format!("Task {} has no stored build request and cannot be resumed", task_id),
+            ).into_response();
+        }
+        Err(e) => {
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                format!("Failed to load build request: {}", e),
+            ).into_response();
+        }
+    }
+    
+    // 与创建任务一样持有服务器锁，避免和 build_package 同时启动任务
+    let server_lock = state.get_server_lock(&task.server);
+    let _guard = server_lock.lock().await;
+    
+    if let Err(e) = task_repo.update_state(task_id, crate::model::state::TaskState::Pending, None).await {
+        return (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("Failed to update task state: {}", e),
+        ).into_response();
+    }
+    
+    tracing::info!("♻️  任务 #{} 已重新排队，将从失败的步骤恢复执行", task_id);
+    
+    match task_repo.has_running_task_on_server(&task.server).await {
+        Ok(true) => {
+            (StatusCode::OK, format!("Task {} queued for resume", task_id)).into_response()
+        }
+        _ => {
+            let app_state = std::sync::Arc::new(state.clone());
+            app_state.start_next_pending_task(task.server.clone()).await;
+            (StatusCode::OK, format!("Task {} resumed", task_id)).into_response()
+        }
+    }
+}
@@ -40,6 +40,8 @@ pub fn create_router(state: AppState) -> Router {
         .route("/delete_task", post(handlers::task::delete_task))
         .route("/download/*file_path", get(handlers::task::download_installer))
         .route("/task_log/:task_id", get(handlers::task::get_task_log))
+        .route("/tasks/:task_id/steps", get(handlers::task::task_steps))
+        .route("/tasks/:task_id/resume", post(handlers::task::resume_task))
         
         // WebSocket 路由
         .route("/ws/task_log/:task_id", axum::routing::get(ws::ws_handler))
@@ -110,11 +110,11 @@ impl AppState {
                             tracing::warn!("任务 #{} 已被标记为失败或取消，跳过启动", next_task_id);
                             return;
                         }
-                    // 构建 BuildRequest（需要从任务信息中恢复）
-                    // 注意：这里需要从 pkg_flag 或其他字段中恢复完整信息
-                    // 为了简化，我们只启动单个架构的任务
+                    // 构建 BuildRequest：优先使用创建任务时保存的完整请求，
+                    // 旧任务没有保存时从任务信息中恢复（只能恢复单个架构的基本参数）
+                    let stored_request = task_repo.get_build_request(next_task_id).await.ok().flatten();
                     if let Some(arch) = &next_task.architecture {
-                        let request = crate::model::build::BuildRequest {
+                        let request = stored_request.unwrap_or_else(|| crate::model::build::BuildRequest {
                             branch: next_task.branch_name.clone(),
                             commit_id: if next_task.commit_id.is_empty() { None } else { Some(next_task.commit_id) },
                             pkg_flag: next_task.pkg_flag.clone(),
@@ -128,7 +128,7 @@ impl AppState {
                             custom_args: None,
                             is_update: false,
                             emails: None,
-                        };
+                        });
                         
                         // 在调用前克隆所有需要的值，确保 Send
                         let task_manager = task_service.manager().clone();
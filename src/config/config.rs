@@ -114,6 +114,10 @@ pub struct PlatformArgs {
 
 #[derive(Debug, Clone, Deserialize, Default)]
 pub struct PlatformBuildSteps {
+    // 所有平台共享的步骤列表，平台/架构未单独配置时使用，
+    // 平台差异通过 skip_if 表达（如 skip_if = "platform!=macos"）
+    #[serde(default)]
+    pub common: Vec<BuildStep>,
     #[serde(default)]
     pub windows: ArchitectureBuildSteps,
     #[serde(default)]
@@ -142,12 +146,28 @@ pub struct ArchitectureBuildSteps {
 #[derive(Debug, Clone, Deserialize)]
 pub struct BuildStep {
     pub name: String,
-    pub step_type: String,  // "git", "ninja", "clean", "gn_gen", "installer"
+    pub step_type: String,  // 见 `model::step::StepKind`：git, clean, gn_gen, ninja, installer, combine, backup
     pub target: Option<String>,  // ninja 目标或 git 操作
     pub state: Option<String>,  // TaskState 名称
-    pub skip_if: Option<String>,  // 跳过条件，如 "target_os=macos", "is_increment=true"
+    pub skip_if: Option<String>,  // 跳过条件表达式，如 "platform=macos and not is_increment=true"，语法见 step::condition
     #[allow(dead_code)]
     pub description: Option<String>,  // 步骤描述
+    #[serde(default)]
+    pub timeout_secs: Option<u64>,  // 单次执行超时（秒），超时会终止整个进程树
+    #[serde(default)]
+    pub retries: u32,  // 失败后的重试次数，默认不重试
+    #[serde(default)]
+    pub retry_backoff_secs: Option<u64>,  // 重试间隔（秒），每次重试翻倍
+    #[serde(default)]
+    pub retry_on: Vec<RetryOn>,  // 只在匹配时重试；为空表示任何失败都重试
+}
+
+/// 重试条件：退出码，或匹配错误输出的正则
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum RetryOn {
+    ExitCode(i32),
+    Pattern(String),
 }
 
 impl AppConfig {
@@ -195,6 +215,11 @@ impl AppConfig {
                 AppConfig::default()
             });
 
+        // 构建步骤必须能被执行器识别，否则 typo 会让构建静默变成空操作
+        crate::service::build::StepRegistry::builtin()
+            .validate_config(&app_config)
+            .with_context(|| format!("配置文件 {} 中的 build_steps 无效", path))?;
+
         // 初始化环境变量
         app_config.init_env();
 
@@ -285,7 +310,40 @@ impl AppConfig {
         })
     }
     
+    /// 列出配置中所有平台、所有架构的构建步骤，返回 (平台, 架构, 步骤列表)
+    pub fn all_build_steps(&self) -> Vec<(&'static str, &'static str, &[BuildStep])> {
+        let platforms = [
+            ("windows", &self.build_steps.windows),
+            ("linux", &self.build_steps.linux),
+            ("macos", &self.build_steps.macos),
+        ];
+        platforms
+            .into_iter()
+            .flat_map(|(platform, arch_steps)| {
+                [
+                    ("x64", arch_steps.x64.as_slice()),
+                    ("x86", arch_steps.x86.as_slice()),
+                    ("arm64", arch_steps.arm64.as_slice()),
+                    ("arm", arch_steps.arm.as_slice()),
+                ]
+                .into_iter()
+                .map(move |(arch, steps)| (platform, arch, steps))
+            })
+            .chain(std::iter::once(("common", "*", self.build_steps.common.as_slice())))
+            .collect()
+    }
+    
+    /// 获取指定架构的构建步骤，平台/架构未配置时回退到 `build_steps.common`
     pub fn get_build_steps(&self, architecture: Option<&str>) -> Vec<BuildStep> {
+        let steps = self.get_platform_build_steps(architecture);
+        if steps.is_empty() {
+            self.build_steps.common.clone()
+        } else {
+            steps
+        }
+    }
+    
+    fn get_platform_build_steps(&self, architecture: Option<&str>) -> Vec<BuildStep> {
         let os = std::env::consts::OS;
         let arch = architecture.unwrap_or("x64");  // 默认使用 x64
         
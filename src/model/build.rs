@@ -1,7 +1,7 @@
 use validator::Validate;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Validate, Deserialize, Clone)]
+#[derive(Debug, Validate, Serialize, Deserialize, Clone)]
 pub struct BuildRequest {
     #[validate(length(min = 1, max = 100))]
     pub branch: String,
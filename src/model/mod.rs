@@ -2,4 +2,5 @@ pub mod task;
 pub mod build;
 pub mod oem;
 pub mod state;
+pub mod step;
 
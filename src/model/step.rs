@@ -0,0 +1,125 @@
+use serde::{Deserialize, Serialize};
+
+/// 构建步骤类型，对应 config.toml 中 `build_steps` 的 `step_type` 字段
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum StepKind {
+    #[serde(rename = "git")]
+    Git,
+
+    #[serde(rename = "clean")]
+    Clean,
+
+    #[serde(rename = "gn_gen")]
+    GnGen,
+
+    #[serde(rename = "ninja")]
+    Ninja,
+
+    #[serde(rename = "installer")]
+    Installer,
+
+    #[serde(rename = "combine")]
+    Combine,
+
+    #[serde(rename = "backup")]
+    Backup,
+}
+
+impl StepKind {
+    pub const ALL: [StepKind; 7] = [
+        StepKind::Git,
+        StepKind::Clean,
+        StepKind::GnGen,
+        StepKind::Ninja,
+        StepKind::Installer,
+        StepKind::Combine,
+        StepKind::Backup,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            StepKind::Git => "git",
+            StepKind::Clean => "clean",
+            StepKind::GnGen => "gn_gen",
+            StepKind::Ninja => "ninja",
+            StepKind::Installer => "installer",
+            StepKind::Combine => "combine",
+            StepKind::Backup => "backup",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Option<Self> {
+        StepKind::ALL.iter().copied().find(|kind| kind.as_str() == s)
+    }
+}
+
+impl std::fmt::Display for StepKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// 单个步骤的执行状态，持久化在 task_step 表中
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StepStatus {
+    #[serde(rename = "pending")]
+    Pending,
+
+    #[serde(rename = "running")]
+    Running,
+
+    #[serde(rename = "ok")]
+    Ok,
+
+    #[serde(rename = "failed")]
+    Failed,
+
+    #[serde(rename = "skipped")]
+    Skipped,
+
+    #[serde(rename = "timed_out")]
+    TimedOut,
+}
+
+impl StepStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            StepStatus::Pending => "pending",
+            StepStatus::Running => "running",
+            StepStatus::Ok => "ok",
+            StepStatus::Failed => "failed",
+            StepStatus::Skipped => "skipped",
+            StepStatus::TimedOut => "timed_out",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "pending" => Some(StepStatus::Pending),
+            "running" => Some(StepStatus::Running),
+            "ok" => Some(StepStatus::Ok),
+            "failed" => Some(StepStatus::Failed),
+            "skipped" => Some(StepStatus::Skipped),
+            "timed_out" => Some(StepStatus::TimedOut),
+            _ => None,
+        }
+    }
+
+    /// 恢复任务时可以直接跳过的状态
+    pub fn is_done(&self) -> bool {
+        matches!(self, StepStatus::Ok | StepStatus::Skipped)
+    }
+}
+
+/// 任务中某个步骤的执行记录
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskStep {
+    pub task_id: i64,
+    pub step_index: i64,
+    pub name: String,
+    pub step_type: String,
+    pub status: StepStatus,
+    pub start_time: Option<String>,
+    pub end_time: Option<String>,
+    pub exit_code: Option<i32>,
+}
@@ -24,6 +24,8 @@ pub struct Task {
     pub build_log: Option<String>,  // 构建日志
     #[serde(default)]
     pub installer_format: Option<String>,  // 安装包格式：dmg 或 pkg
+    #[serde(default)]
+    pub out_dir: Option<String>,  // 构建输出目录（恢复任务时复用）
 }
 
 #[derive(Debug, Deserialize)]
@@ -75,6 +77,7 @@ impl Default for Task {
             architecture: None,
             build_log: None,
             installer_format: None,
+            out_dir: None,
         }
     }
 }
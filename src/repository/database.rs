@@ -65,6 +65,27 @@ pub async fn init_db(config: &AppConfig) -> Result<Option<SqlitePool>> {
     let _ = pool.execute("ALTER TABLE pkg ADD COLUMN architecture TEXT").await;
     let _ = pool.execute("ALTER TABLE pkg ADD COLUMN build_log TEXT").await;
     let _ = pool.execute("ALTER TABLE pkg ADD COLUMN installer_format TEXT").await;
+    let _ = pool.execute("ALTER TABLE pkg ADD COLUMN out_dir TEXT").await;
+    let _ = pool.execute("ALTER TABLE pkg ADD COLUMN request_json TEXT").await;
+    
+    // 每个任务的步骤执行记录（用于失败后从失败步骤恢复）
+    pool.execute(
+        r#"
+        CREATE TABLE IF NOT EXISTS task_step (
+            task_id INTEGER NOT NULL,
+            step_index INTEGER NOT NULL,
+            name TEXT NOT NULL,
+            step_type TEXT NOT NULL,
+            status TEXT NOT NULL,
+            start_time TEXT,
+            end_time TEXT,
+            exit_code INTEGER,
+            PRIMARY KEY (task_id, step_index)
+        );
+        "#,
+    )
+    .await
+    .context("Failed to create task_step table")?;
     
     Ok(Some(pool))
 }
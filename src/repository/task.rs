@@ -2,9 +2,11 @@ use sqlx::SqlitePool;
 use crate::error::{AppError, AppResult};
 use crate::model::task::{Task, CreateTask};
 use crate::model::state::TaskState;
+use crate::model::build::BuildRequest;
+use crate::model::step::{StepStatus, TaskStep};
 
 const TASKLIST_QUERY: &str = r#"
-  SELECT id, start_time, branch_name, end_time, oem_name, commit_id, pkg_flag, is_signed, is_increment, storage_path, installer, state, server, parent_id, architecture, build_log, installer_format
+  SELECT id, start_time, branch_name, end_time, oem_name, commit_id, pkg_flag, is_signed, is_increment, storage_path, installer, state, server, parent_id, architecture, build_log, installer_format, out_dir
   FROM pkg
   ORDER BY COALESCE(parent_id, id) DESC, id ASC
 "#;
@@ -270,6 +272,14 @@ impl TaskRepository {
     }
     
     pub async fn delete(&self, id: i64) -> AppResult<()> {
+        // 删除父任务及子任务的步骤记录
+        sqlx::query("DELETE FROM task_step WHERE task_id = ? OR task_id IN (SELECT id FROM pkg WHERE parent_id = ?)")
+            .bind(id)
+            .bind(id)
+            .execute(&self.pool)
+            .await
+            .map_err(AppError::Database)?;
+        
         // 先删除所有子任务（级联删除）
         sqlx::query("DELETE FROM pkg WHERE parent_id = ?")
             .bind(id)
@@ -319,6 +329,7 @@ impl TaskRepository {
             architecture: row.try_get("architecture").ok(),
             build_log: row.try_get("build_log").ok(),
             installer_format: row.try_get("installer_format").ok().flatten(),
+            out_dir: row.try_get("out_dir").ok().flatten(),
         }
     }
     
@@ -376,6 +387,12 @@ impl TaskRepository {
             .await
             .map_err(AppError::Database)?;
         
+        // 服务重启时仍在 running 的步骤也一并标记为失败，恢复任务时会从这里重新开始
+        sqlx::query("UPDATE task_step SET status = 'failed', end_time = datetime('now', 'localtime') WHERE status = 'running'")
+            .execute(pool)
+            .await
+            .map_err(AppError::Database)?;
+        
         Ok(result.rows_affected())
     }
     
@@ -418,6 +435,154 @@ impl TaskRepository {
         
         Ok(all_completed)
     }
+    
+    /// 保存任务的构建请求（用于排队启动和恢复任务时还原完整参数）
+    pub async fn save_build_request(&self, task_id: i64, request: &BuildRequest) -> AppResult<()> {
+        let request_json = serde_json::to_string(request)
+            .map_err(|e| AppError::Build(format!("序列化构建请求失败: {}", e)))?;
+        
+        sqlx::query("UPDATE pkg SET request_json = ? WHERE id = ?")
+            .bind(request_json)
+            .bind(task_id)
+            .execute(&self.pool)
+            .await
+            .map_err(AppError::Database)?;
+        
+        Ok(())
+    }
+    
+    /// 读取任务保存的构建请求，旧任务没有保存时返回 None
+    pub async fn get_build_request(&self, task_id: i64) -> AppResult<Option<BuildRequest>> {
+        let request_json: Option<Option<String>> = sqlx::query_scalar("SELECT request_json FROM pkg WHERE id = ?")
+            .bind(task_id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(AppError::Database)?;
+        
+        let request = request_json.flatten().and_then(|json| {
+            serde_json::from_str(&json)
+                .map_err(|e| tracing::warn!("⚠️  任务 #{} 的构建请求无法解析: {}", task_id, e))
+                .ok()
+        });
+        
+        Ok(request)
+    }
+    
+    pub async fn update_out_dir(&self, task_id: i64, out_dir: &str) -> AppResult<()> {
+        sqlx::query("UPDATE pkg SET out_dir = ? WHERE id = ?")
+            .bind(out_dir)
+            .bind(task_id)
+            .execute(&self.pool)
+            .await
+            .map_err(AppError::Database)?;
+        
+        Ok(())
+    }
+    
+    /// 获取任务的步骤执行记录（按步骤顺序）
+    pub async fn list_steps(&self, task_id: i64) -> AppResult<Vec<TaskStep>> {
+        use sqlx::Row;
+        
+        let rows = sqlx::query(
+            "SELECT task_id, step_index, name, step_type, status, start_time, end_time, exit_code
+             FROM task_step
+             WHERE task_id = ?
+             ORDER BY step_index ASC"
+        )
+            .bind(task_id)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(AppError::Database)?;
+        
+        let steps = rows.iter()
+            .map(|row| {
+                let status: String = row.get("status");
+                TaskStep {
+                    task_id: row.get("task_id"),
+                    step_index: row.get("step_index"),
+                    name: row.get("name"),
+                    step_type: row.get("step_type"),
+                    status: StepStatus::from_str(&status).unwrap_or(StepStatus::Pending),
+                    start_time: row.try_get("start_time").ok().flatten(),
+                    end_time: row.try_get("end_time").ok().flatten(),
+                    exit_code: row.try_get("exit_code").ok().flatten(),
+                }
+            })
+            .collect();
+        
+        Ok(steps)
+    }
+    
+    /// 从 from_index 开始重新登记步骤（之前的记录保留，用于恢复任务）
+    pub async fn reset_steps(&self, task_id: i64, from_index: usize, steps: &[(String, String)]) -> AppResult<()> {
+        let mut tx = self.pool.begin().await.map_err(AppError::Database)?;
+        
+        sqlx::query("DELETE FROM task_step WHERE task_id = ? AND step_index >= ?")
+            .bind(task_id)
+            .bind(from_index as i64)
+            .execute(&mut *tx)
+            .await
+            .map_err(AppError::Database)?;
+        
+        for (index, (name, step_type)) in steps.iter().enumerate().skip(from_index) {
+            sqlx::query(
+                "INSERT OR REPLACE INTO task_step (task_id, step_index, name, step_type, status)
+                 VALUES (?, ?, ?, ?, ?)"
+            )
+                .bind(task_id)
+                .bind(index as i64)
+                .bind(name)
+                .bind(step_type)
+                .bind(StepStatus::Pending.as_str())
+                .execute(&mut *tx)
+                .await
+                .map_err(AppError::Database)?;
+        }
+        
+        tx.commit().await.map_err(AppError::Database)?;
+        Ok(())
+    }
+    
+    /// 标记步骤开始执行
+    pub async fn start_step(&self, task_id: i64, step_index: usize) -> AppResult<()> {
+        sqlx::query(
+            "UPDATE task_step
+             SET status = ?, start_time = datetime('now', 'localtime'), end_time = NULL, exit_code = NULL
+             WHERE task_id = ? AND step_index = ?"
+        )
+            .bind(StepStatus::Running.as_str())
+            .bind(task_id)
+            .bind(step_index as i64)
+            .execute(&self.pool)
+            .await
+            .map_err(AppError::Database)?;
+        
+        Ok(())
+    }
+    
+    /// 标记步骤结束（ok / failed / skipped）
+    pub async fn finish_step(
+        &self,
+        task_id: i64,
+        step_index: usize,
+        status: StepStatus,
+        exit_code: Option<i32>,
+    ) -> AppResult<()> {
+        sqlx::query(
+            "UPDATE task_step
+             SET status = ?, end_time = datetime('now', 'localtime'), exit_code = ?
+             WHERE task_id = ? AND step_index = ?"
+        )
+            .bind(status.as_str())
+            .bind(exit_code)
+            .bind(task_id)
+            .bind(step_index as i64)
+            .execute(&self.pool)
+            .await
+            .map_err(AppError::Database)?;
+        
+        Ok(())
+    }
 }
 
 // 为 TaskState 实现 FromStr
@@ -440,6 +605,7 @@ impl std::str::FromStr for TaskState {
             "backup" => Ok(TaskState::BackingUp),
             "success" => Ok(TaskState::Success),
             "failed" => Ok(TaskState::Failed),
+            "cancelled" => Ok(TaskState::Cancelled),
             _ => Err(format!("Unknown state: {}", s)),
         }
     }
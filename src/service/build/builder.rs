@@ -1,8 +1,9 @@
 use std::path::Path;
-use std::process::Command;
+use tokio::process::Command;
 use anyhow::{Context, Result};
 use crate::config::AppConfig;
 use crate::model::build::BuildRequest;
+use crate::util::process;
 
 #[cfg(target_os = "windows")]
 mod os {
@@ -167,11 +168,12 @@ impl ProjectBuilder {
         tracing::info!("执行命令: {}", gn_command);
         
         let start_time = std::time::Instant::now();
-        let output = Command::new(os::SHELL[0])
-            .arg(os::SHELL[1])
-            .arg(&gn_command)
-            .current_dir(src_path)
-            .output()
+        let output = process::output(
+            Command::new(os::SHELL[0])
+                .arg(os::SHELL[1])
+                .arg(&gn_command)
+                .current_dir(src_path)
+        ).await
             .context("Failed to execute gn gen")?;
         
         let duration = start_time.elapsed();
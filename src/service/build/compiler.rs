@@ -7,6 +7,7 @@ use anyhow::{Context, Result};
 use crate::config::AppConfig;
 use crate::repository::task::TaskRepository;
 use crate::api::ws::WsManager;
+use crate::util::process;
 
 #[cfg(target_os = "windows")]
 mod os {
@@ -62,14 +63,15 @@ impl Compiler {
             
             let start_time = std::time::Instant::now();
             
-            // 使用 tokio::process::Command 来实时捕获输出
-            let mut child = Command::new(os::SHELL[0])
-                .arg(os::SHELL[1])
-                .arg(&command)
-                .current_dir(src_path)
-                .stdout(std::process::Stdio::piped())
-                .stderr(std::process::Stdio::piped())
-                .spawn()
+            // 使用 tokio::process::Command 来实时捕获输出（独立进程组，步骤超时时整个进程树会被终止）
+            let mut child = process::spawn_group(
+                Command::new(os::SHELL[0])
+                    .arg(os::SHELL[1])
+                    .arg(&command)
+                    .current_dir(src_path)
+                    .stdout(std::process::Stdio::piped())
+                    .stderr(std::process::Stdio::piped())
+            )
                 .context(format!("Failed to spawn ninja for target: {}", target))?;
             
             let mut stdout_lines = Vec::new();
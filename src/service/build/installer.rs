@@ -1,7 +1,8 @@
 use std::path::Path;
-use std::process::Command;
+use tokio::process::Command;
 use anyhow::{Context, Result};
 use crate::config::AppConfig;
+use crate::util::process;
 
 
 
@@ -57,16 +58,13 @@ impl InstallerBuilder {
             tracing::info!("═══════════════════════════════════════════════════════");
             
             let start_time = std::time::Instant::now();
-            let output = Command::new(os::SHELL[0])
-                .arg(os::SHELL[1])
-                .arg(&command)
-                .current_dir(src_path)
-                .stdout(std::process::Stdio::piped())
-                .stderr(std::process::Stdio::piped())
-                .spawn()
-                .context(format!("Failed to spawn ninja for target: {}", target))?
-                .wait_with_output()
-                .context(format!("Failed to wait for ninja: {}", target))?;
+            let output = process::output(
+                Command::new(os::SHELL[0])
+                    .arg(os::SHELL[1])
+                    .arg(&command)
+                    .current_dir(src_path)
+            ).await
+                .context(format!("Failed to run ninja for target: {}", target))?;
             
             let duration = start_time.elapsed();
             let exit_code = output.status.code().unwrap_or(-1);
@@ -4,9 +4,11 @@ pub mod installer;
 pub mod service;
 pub mod dmg;
 pub mod pkg;
+pub mod step;
 
 
 pub use builder::*;
 pub use compiler::*;
 pub use installer::*;
 pub use service::*;
+pub use step::{StepContext, StepRegistry};
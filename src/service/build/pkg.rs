@@ -2,7 +2,8 @@ use std::path::Path;
 use anyhow::{Result, Context};
 use crate::service::build::installer::InstallerBuilder;
 use tokio::fs;
-use std::process::Command;
+use tokio::process::Command;
+use crate::util::process;
 
 #[cfg(target_os = "macos")]
 pub async fn create(builder: &InstallerBuilder, src_path: &Path, out_dir: &str) -> Result<()> {
@@ -46,10 +47,11 @@ pub async fn create(builder: &InstallerBuilder, src_path: &Path, out_dir: &str)
     // 使用 ditto 复制 .app 到临时目录（保留符号链接，不展开）
     tracing::info!("📦 使用 ditto 复制应用到临时目录: {}", temp_dir.display());
     let temp_app_path = temp_dir.join(&app_name);
-    let ditto_output = Command::new("ditto")
-        .arg(&app_path)
-        .arg(&temp_app_path)
-        .output()
+    let ditto_output = process::output(
+        Command::new("ditto")
+            .arg(&app_path)
+            .arg(&temp_app_path)
+    ).await
         .context("Failed to execute ditto")?;
     
     if !ditto_output.status.success() {
@@ -87,7 +89,7 @@ pub async fn create(builder: &InstallerBuilder, src_path: &Path, out_dir: &str)
     tracing::info!("📝 创建 component.plist，禁用 relocate");
     
     // 使用 pkgbuild 创建 PKG（--root + --component-plist）
-    let output = Command::new("pkgbuild")
+    let output = process::output(Command::new("pkgbuild")
         .arg("--root")
         .arg(&temp_dir)
         .arg("--component-plist")
@@ -101,7 +103,7 @@ pub async fn create(builder: &InstallerBuilder, src_path: &Path, out_dir: &str)
         .arg("--ownership")
         .arg("recommended")
         .arg(&pkg_path)
-        .output()
+    ).await
         .context("Failed to execute pkgbuild")?;
     
     // 清理临时文件
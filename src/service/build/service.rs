@@ -4,12 +4,17 @@ use anyhow::Result;
 use crate::config::AppConfig;
 use crate::model::build::BuildRequest;
 use crate::repository::task::TaskRepository;
-use crate::service::build::{ProjectBuilder, Compiler, InstallerBuilder};
+use crate::service::build::{ProjectBuilder, Compiler, InstallerBuilder, StepContext, StepRegistry};
+use crate::service::build::step::StepOutcome;
+use crate::service::build::step::condition::{Condition, ConditionVars};
+use crate::service::build::step::policy::{exit_code_of, StepPolicy, StepTimedOut};
 use crate::service::backup::BackupManager;
 use crate::service::email::EmailSender;
 use crate::service::task::TaskManager;
 use crate::util::{git, time};
 use crate::model::task::CreateTask;
+use crate::model::step::{StepKind, StepStatus, TaskStep};
+use crate::config::BuildStep;
 
 #[derive(Clone)]
 pub struct BuildService {
@@ -20,6 +25,7 @@ pub struct BuildService {
     backup_manager: BackupManager,
     email_sender: EmailSender,
     ws_manager: Option<crate::api::ws::WsManager>,
+    registry: StepRegistry,
 }
 
 impl BuildService {
@@ -33,6 +39,7 @@ impl BuildService {
             backup_manager: BackupManager::new(config.clone()),
             email_sender: EmailSender::new(config),
             ws_manager: None,
+            registry: StepRegistry::builtin(),
         }
     }
     
@@ -72,6 +79,7 @@ impl BuildService {
         };
         
         let task_id = task_repo.create(&create_task).await?;
+        task_repo.save_build_request(task_id, &request).await?;
         // 确保状态为 pending（数据库默认状态）
         task_repo.update_state(task_id, crate::model::state::TaskState::Pending, None).await?;
         
@@ -125,17 +133,11 @@ impl BuildService {
         task_repo.update_state(task_id, crate::model::state::TaskState::StartBuild, None).await?;
         
         // 启动异步构建
-        let config_clone = self.config.clone();
+        let service_clone = self.clone();
         let request_clone = request.clone();
-        let builder_clone = self.builder.clone();
-        let compiler_clone = self.compiler.clone();
-        let installer_clone = self.installer.clone();
-        let backup_clone = self.backup_manager.clone();
-        let email_clone = self.email_sender.clone();
         
         let task_repo_clone_owned = (*task_repo).clone();
         let task_repo_for_fail = task_repo_clone_owned.clone(); // 为错误处理克隆一份
-        let ws_manager_clone = self.ws_manager.clone();
         let server = request.server.clone();
         let app_state = on_complete;
         
@@ -145,16 +147,10 @@ impl BuildService {
         
         task_manager.start_task(task_id, cancelled_flag.clone(), async move {
             let result = do_build(
-                config_clone,
+                service_clone,
                 request_clone,
                 task_repo_clone_owned,
                 task_id,
-                builder_clone,
-                compiler_clone,
-                installer_clone,
-                backup_clone,
-                email_clone,
-                ws_manager_clone,
                 Some(cancelled_flag),
             ).await;
             
@@ -222,6 +218,7 @@ impl BuildService {
         };
         
         let task_id = task_repo.create(&create_task).await?;
+        task_repo.save_build_request(task_id, &request).await?;
         
         // 确保任务状态为 pending（数据库默认状态）
         task_repo.update_state(task_id, crate::model::state::TaskState::Pending, None).await?;
@@ -258,32 +255,19 @@ impl BuildService {
         task_repo.update_state(task_id, crate::model::state::TaskState::StartBuild, None).await?;
         
         // 启动异步构建
-        let config_clone = self.config.clone();
+        let service_clone = self.clone();
         let request_clone = request.clone();
         let task_repo_clone_owned = (*task_repo).clone();
-        let builder_clone = self.builder.clone();
-        let compiler_clone = self.compiler.clone();
-        let installer_clone = self.installer.clone();
-        let backup_clone = self.backup_manager.clone();
-        let email_clone = self.email_sender.clone();
-        
-        let ws_manager_clone = self.ws_manager.clone();
         
         // 创建取消标志（在 start_task 之前创建，确保可以被 cancel_task 找到）
         let cancelled_flag = task_manager.create_cancelled_flag(task_id);
         
         task_manager.start_task(task_id, cancelled_flag.clone(), async move {
             do_build(
-                config_clone,
+                service_clone,
                 request_clone,
                 task_repo_clone_owned,
                 task_id,
-                builder_clone,
-                compiler_clone,
-                installer_clone,
-                backup_clone,
-                email_clone,
-                ws_manager_clone,
                 Some(cancelled_flag),
             ).await
         }).await?;
@@ -345,18 +329,13 @@ impl BuildService {
 }
 
 async fn do_build(
-    config: Arc<AppConfig>,
+    service: BuildService,
     request: BuildRequest,
     task_repo: TaskRepository,
     task_id: i64,
-    builder: ProjectBuilder,
-    compiler: Compiler,
-    installer: InstallerBuilder,
-    _backup_manager: BackupManager,
-    email_sender: EmailSender,
-    ws_manager: Option<crate::api::ws::WsManager>,
     cancelled_flag: Option<Arc<std::sync::atomic::AtomicBool>>,
 ) -> Result<()> {
+    let config = service.config.clone();
     let src_path = Path::new(config.get_src_path()?);
     let build_start_time = std::time::Instant::now();
     
@@ -381,10 +360,6 @@ async fn do_build(
     tracing::info!("📋 ================================");
     tracing::info!("═══════════════════════════════════════════════════════\n");
     
-    // 生成输出目录名称
-    let out_dir = generate_out_dir(&config, &request)?;
-    tracing::info!("📂 输出目录: {}\n", out_dir);
-    
     // 获取配置的构建步骤（根据架构）
     let architecture = request.architectures.first().map(|s| s.as_str());
     let build_steps = config.get_build_steps(architecture);
@@ -392,27 +367,71 @@ async fn do_build(
         tracing::info!("🏗️  构建架构: {}\n", arch);
     }
     let total_steps = build_steps.len();
-    let mut commit_id = String::new();
+    
+    // 之前执行过的任务（恢复任务）从第一个未完成的步骤继续，并复用上次的输出目录和 commit_id
+    let task = task_repo.find_by_id(task_id).await?;
+    let previous_steps = task_repo.list_steps(task_id).await?;
+    let resume_from = resume_index(&build_steps, &previous_steps);
+    let mut request = request;
+    if resume_from > 0 {
+        tracing::info!("♻️  任务 #{} 从步骤 {}/{} 恢复执行", task_id, resume_from + 1, total_steps);
+        if !task.commit_id.is_empty() {
+            request.commit_id = Some(task.commit_id.clone());
+        }
+    }
+    
+    // 生成输出目录名称
+    let out_dir = match task.out_dir.as_deref().filter(|dir| resume_from > 0 && !dir.is_empty()) {
+        Some(dir) => dir.to_string(),
+        None => generate_out_dir(&config, &request)?,
+    };
+    tracing::info!("📂 输出目录: {}\n", out_dir);
+    task_repo.update_out_dir(task_id, &out_dir).await?;
+    
+    let step_names: Vec<(String, String)> = build_steps.iter()
+        .map(|step| (step.name.clone(), step.step_type.clone()))
+        .collect();
+    task_repo.reset_steps(task_id, resume_from, &step_names).await?;
+    
+    let ctx = StepContext::new(
+        config.clone(),
+        request.clone(),
+        task_id,
+        task_repo.clone(),
+        src_path.to_path_buf(),
+        out_dir,
+        service.builder.clone(),
+        service.compiler.clone(),
+        service.installer.clone(),
+        service.backup_manager.clone(),
+        service.ws_manager.clone(),
+        cancelled_flag.clone(),
+    );
+    if resume_from > 0 && !task.commit_id.is_empty() {
+        ctx.set_commit_id(&task.commit_id);
+    }
     
     // 遍历执行每个构建步骤
-    for (index, step) in build_steps.iter().enumerate() {
+    for (index, step) in build_steps.iter().enumerate().skip(resume_from) {
         // 在每个步骤开始前检查取消标志
-        if let Some(flag) = &cancelled_flag {
-            if flag.load(std::sync::atomic::Ordering::Relaxed) {
-                tracing::warn!("⚠️  任务 #{} 已取消，停止执行后续步骤", task_id);
-                eprintln!("⚠️  任务 #{} 已取消，停止执行后续步骤", task_id);
-                return Err(anyhow::anyhow!("Task cancelled"));
-            }
+        if ctx.is_cancelled() {
+            tracing::warn!("⚠️  任务 #{} 已取消，停止执行后续步骤", task_id);
+            eprintln!("⚠️  任务 #{} 已取消，停止执行后续步骤", task_id);
+            return Err(anyhow::anyhow!("Task cancelled"));
         }
         
         let step_num = index + 1;
         
         // 检查跳过条件
-        if should_skip_step(&step, &request) {
+        if should_skip_step(step, &request)? {
             tracing::info!("⏭️  步骤 {}/{}: 跳过 {}（条件不满足）\n", step_num, total_steps, step.name);
+            task_repo.finish_step(task_id, index, StepStatus::Skipped, None).await?;
             continue;
         }
         
+        // 查找执行器（配置加载时已校验，这里失败说明配置被绕过，直接让构建失败）
+        let executor = service.registry.resolve(step)?;
+        
         // 更新任务状态
         if let Some(state_str) = &step.state {
             if let Some(state) = crate::model::state::TaskState::from_str(state_str) {
@@ -422,145 +441,46 @@ async fn do_build(
         
         tracing::info!("步骤 {}/{}: {}", step_num, total_steps, step.name);
         
-        // 再次检查取消标志（在步骤执行前）
-        if let Some(flag) = &cancelled_flag {
-            if flag.load(std::sync::atomic::Ordering::Relaxed) {
-                tracing::warn!("任务 #{} 已取消，停止执行步骤: {}", task_id, step.name);
-                return Err(anyhow::anyhow!("Task cancelled"));
-            }
-        }
-        
         let step_start = std::time::Instant::now();
+        task_repo.start_step(task_id, index).await?;
         
-        // 根据步骤类型执行相应操作
-        let step_result = match step.step_type.as_str() {
-            "git" => {
-                match step.target.as_deref() {
-                    Some("update") => {
-                        git::update_code(
-                            src_path,
-                            &request.branch,
-                            request.commit_id.as_deref(),
-                        ).await
-                    },
-                    Some("get_commit_id") => {
-                        let id = git::get_commit_id(src_path).await?;
-                        commit_id = id.clone();
-                        tracing::info!("✅ Commit ID: {}\n", commit_id);
-                        
-                        // 在第一次获取 commit_id 时，立即更新父任务和所有子任务的 commit_id
-                        if let Err(e) = task_repo.update_family_commit_id(task_id, &commit_id).await {
-                            tracing::warn!("⚠️  更新父子任务 commit_id 失败: {}", e);
-                        }
-                        
-                        // 更新当前任务的状态
-                        if let Some(state_str) = &step.state {
-                            if let Some(state) = crate::model::state::TaskState::from_str(state_str) {
-                                task_repo.update_state(task_id, state, Some(&commit_id)).await?;
-                            }
-                        }
-                        Ok(())
-                    },
-                    _ => {
-                        tracing::warn!("⚠️  未知的 git 操作: {:?}", step.target);
-                        Ok(())
+        // 根据步骤类型执行相应操作（按步骤配置的超时与重试策略）
+        let policy = StepPolicy::from_step(step)?;
+        let status = match policy.run(executor.as_ref(), &ctx, step).await {
+            Err(e) => {
+                let status = if e.downcast_ref::<StepTimedOut>().is_some() {
+                    tracing::error!("⏰ {}", e);
+                    let log_line = format!("[{}] {}", step.name, e);
+                    let _ = task_repo.append_build_log(task_id, &log_line).await;
+                    if let Some(ws) = &ctx.ws_manager {
+                        ws.broadcast_log(task_id, log_line, false);
                     }
-                }
-            },
-            "clean" => {
-                builder.clean(src_path, &out_dir, request.is_increment).await
-            },
-            "gn_gen" => {
-                builder.generate(src_path, &out_dir, &request).await
-            },
-            "ninja" => {
-                if let Some(target) = &step.target {
-                    compiler.build_targets(src_path, &out_dir, &[target], &step.name, Some(task_id), Some(&task_repo), ws_manager.as_ref(), cancelled_flag.clone()).await
+                    StepStatus::TimedOut
                 } else {
-                    Ok(())
-                }
-            },
-            "installer" => {
-                // 检查是否是子任务，如果是子任务且是 macOS 平台，则跳过 installer（组合任务会在父任务中执行）
-                let task = task_repo.find_by_id(task_id).await?;
-                if task.parent_id.is_some() && request.platform == "macos" {
-                    // 这是 macOS 的子任务，跳过 installer，等待父任务的组合步骤
-                    tracing::info!("⏭️  子任务跳过 installer（macOS 组合任务将在父任务中执行）");
-                    return Ok(());
-                }
-                installer.build_installer(src_path, &out_dir, request.installer_format.as_deref()).await
-            },
-            "combine" => {
-                // 组合步骤：仅用于父任务，组合多个架构的 app 并生成 universal pkg
-                if request.platform != "macos" {
-                    return Err(anyhow::anyhow!("组合任务仅支持 macOS"));
-                }
-                
-                if request.architectures.len() < 2 {
-                    return Err(anyhow::anyhow!("组合任务需要至少2个架构"));
-                }
-                
-                // 检查所有子任务是否都完成了 build chrome
-                let task = task_repo.find_by_id(task_id).await?;
-                if task.parent_id.is_some() {
-                    return Err(anyhow::anyhow!("组合步骤只能在父任务中执行"));
-                }
-                
-                // 获取所有子任务
-                let children = task_repo.get_child_tasks(task_id).await?;
-                if children.len() < 2 {
-                    return Err(anyhow::anyhow!("组合任务需要至少2个子任务"));
-                }
-                
-                // 检查所有子任务是否都完成了 build chrome
-                let all_completed = children.iter().all(|child| {
-                    matches!(
-                        child.state,
-                        crate::model::state::TaskState::BuildingChrome |
-                        crate::model::state::TaskState::Combining |
-                        crate::model::state::TaskState::BuildingInstaller |
-                        crate::model::state::TaskState::Signing |
-                        crate::model::state::TaskState::BackingUp |
-                        crate::model::state::TaskState::Success
-                    )
-                });
-                
-                if !all_completed {
-                    return Err(anyhow::anyhow!("等待所有子任务完成 build chrome"));
+                    if e.to_string().contains("cancelled") {
+                        tracing::warn!("⚠️  步骤 {} 被取消", step.name);
+                        eprintln!("⚠️  步骤 {} 被取消", step.name);
+                    }
+                    StepStatus::Failed
+                };
+                if let Err(update_err) = task_repo.finish_step(task_id, index, status, exit_code_of(&e)).await {
+                    tracing::warn!("⚠️  更新步骤状态失败: {}", update_err);
                 }
-                
-                // 执行组合
-                installer.combine_universal_pkg(src_path, &request.architectures).await
+                return Err(e);
             },
-            "backup" => {
-                // TODO: 实现备份逻辑
-                tracing::info!("⏭️  备份功能待实现");
-                Ok(())
+            Ok(StepOutcome::Skipped(reason)) => {
+                tracing::info!("⏭️  {} 已跳过：{}", step.name, reason);
+                StepStatus::Skipped
             },
-            _ => {
-                tracing::warn!("⚠️  未知的步骤类型: {}", step.step_type);
-                Ok(())
-            }
+            Ok(StepOutcome::Done) => StepStatus::Ok,
         };
-        
-        // 检查步骤执行结果，如果被取消则立即返回
-        match step_result {
-            Err(e) if e.to_string().contains("cancelled") => {
-                tracing::warn!("⚠️  步骤 {} 被取消", step.name);
-                eprintln!("⚠️  步骤 {} 被取消", step.name);
-                return Err(e);
-            },
-            Err(e) => return Err(e),
-            Ok(()) => {},
-        }
+        task_repo.finish_step(task_id, index, status, None).await?;
         
         // 步骤完成后再次检查取消标志
-        if let Some(flag) = &cancelled_flag {
-            if flag.load(std::sync::atomic::Ordering::Relaxed) {
-                tracing::warn!("⚠️  任务 #{} 已取消，停止执行后续步骤", task_id);
-                eprintln!("⚠️  任务 #{} 已取消，停止执行后续步骤", task_id);
-                return Err(anyhow::anyhow!("Task cancelled"));
-            }
+        if ctx.is_cancelled() {
+            tracing::warn!("⚠️  任务 #{} 已取消，停止执行后续步骤", task_id);
+            eprintln!("⚠️  任务 #{} 已取消，停止执行后续步骤", task_id);
+            return Err(anyhow::anyhow!("Task cancelled"));
         }
         
         let step_duration = step_start.elapsed();
@@ -570,7 +490,7 @@ async fn do_build(
         let task = task_repo.find_by_id(task_id).await?;
         if let Some(parent_id) = task.parent_id {
             // 这是子任务，检查是否刚完成 build chrome
-            if step.step_type == "ninja" && step.target.as_deref() == Some("chrome") {
+            if executor.kind() == StepKind::Ninja && step.target.as_deref() == Some("chrome") {
                 // 检查所有子任务是否都完成了 build chrome
                 if let Ok(all_completed) = task_repo.all_children_completed_chrome(parent_id).await {
                     if all_completed {
@@ -610,15 +530,7 @@ async fn do_build(
                                 };
                                 
                                 // 启动父任务的组合步骤
-                                let build_service_clone = BuildService {
-                                    config: config.clone(),
-                                    builder: builder.clone(),
-                                    compiler: compiler.clone(),
-                                    installer: installer.clone(),
-                                    backup_manager: _backup_manager.clone(),
-                                    email_sender: email_sender.clone(),
-                                    ws_manager: ws_manager.clone(),
-                                };
+                                let build_service_clone = service.clone();
                                 
                                 // 异步启动父任务的组合步骤（不阻塞当前任务）
                                 let task_repo_clone = task_repo.clone();
@@ -656,6 +568,7 @@ async fn do_build(
     }
     
     // 确保有 commit_id
+    let mut commit_id = ctx.commit_id();
     if commit_id.is_empty() {
         commit_id = git::get_commit_id(src_path).await?;
     }
@@ -684,7 +597,7 @@ async fn do_build(
     if let Some(emails) = &request.emails {
         if !emails.is_empty() {
             let emails_str = emails.join(",");
-            if let Err(e) = email_sender.send_notification(
+            if let Err(e) = service.email_sender.send_notification(
                 task_id,
                 &request,
                 Some(&emails_str),
@@ -750,17 +663,26 @@ fn generate_out_dir(config: &AppConfig, request: &BuildRequest) -> Result<String
     Ok(out_dir)
 }
 
-/// 检查是否应该跳过步骤
-fn should_skip_step(step: &crate::config::BuildStep, request: &BuildRequest) -> bool {
-    if let Some(skip_if) = &step.skip_if {
-        // 解析跳过条件，格式如 "is_update=false", "target_os=macos"
-        if skip_if.contains("is_update=") {
-            let should_update = skip_if.contains("is_update=false");
-            return should_update && !request.is_update;
-        }
-        // 可以添加更多条件判断
+/// 计算恢复任务时的起始步骤：跳过开头那些与上次记录一致且已完成的步骤
+/// （配置变更导致步骤名或类型不一致时，从不一致的位置重新执行）
+fn resume_index(steps: &[BuildStep], records: &[TaskStep]) -> usize {
+    steps.iter()
+        .zip(records.iter())
+        .take_while(|(step, record)| {
+            record.name == step.name && record.step_type == step.step_type && record.status.is_done()
+        })
+        .count()
+}
+
+/// 检查是否应该跳过步骤（skip_if 语法见 `step::condition`）
+fn should_skip_step(step: &BuildStep, request: &BuildRequest) -> Result<bool> {
+    match &step.skip_if {
+        Some(skip_if) => {
+            let condition = Condition::parse(skip_if)?;
+            Ok(condition.eval(&ConditionVars::from_request(request)))
+        },
+        None => Ok(false),
     }
-    false
 }
 
 // Clone 实现已移到各自的模块中
@@ -0,0 +1,372 @@
+//! `BuildStep.skip_if` 条件表达式
+//!
+//! 语法（关键字不区分大小写）：
+//!
+//! ```text
+//! expr      := and_expr ( ("or" | "||") and_expr )*
+//! and_expr  := unary ( ("and" | "&&") unary )*
+//! unary     := ("not" | "!") unary | "(" expr ")" | predicate
+//! predicate := VAR ("=" | "==" | "!=") VALUE       // 相等比较
+//!            | VAR ("~=" | "!~") PATTERN           // glob 匹配（* 和 ?）
+//!            | "custom_args" "contains" PATTERN    // 任一自定义参数匹配
+//! ```
+//!
+//! 可用变量：`platform`（别名 `target_os`）、`arch`（别名 `architecture`、
+//! `target_cpu`）、`branch`、`is_increment`、`is_signed`、`is_update`、
+//! `installer_format`。值可以是裸词，含空格或 `=` 的值需要用引号，例如
+//! `custom_args contains "is_debug=true"`。
+//!
+//! 示例：`platform=macos and not arch=arm64`、`branch ~= "release/*" or is_increment=true`
+
+use anyhow::Result;
+use crate::model::build::BuildRequest;
+
+/// 条件求值时可见的变量
+#[derive(Debug, Clone, Default)]
+pub struct ConditionVars {
+    pub platform: String,
+    pub arch: String,
+    pub branch: String,
+    pub is_increment: bool,
+    pub is_signed: bool,
+    pub is_update: bool,
+    pub installer_format: String,
+    pub custom_args: Vec<String>,
+}
+
+impl ConditionVars {
+    /// 平台取构建服务器自身的操作系统（构建总是在目标平台的机器上执行）
+    pub fn from_request(request: &BuildRequest) -> Self {
+        Self {
+            platform: std::env::consts::OS.to_string(),
+            arch: request.architectures.first().cloned().unwrap_or_default(),
+            branch: request.branch.clone(),
+            is_increment: request.is_increment,
+            is_signed: request.is_signed,
+            is_update: request.is_update,
+            installer_format: request.installer_format.clone().unwrap_or_default(),
+            custom_args: request.custom_args.clone().unwrap_or_default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Var {
+    Platform,
+    Arch,
+    Branch,
+    IsIncrement,
+    IsSigned,
+    IsUpdate,
+    InstallerFormat,
+}
+
+impl Var {
+    fn parse(name: &str) -> Option<Self> {
+        match name {
+            "platform" | "target_os" => Some(Var::Platform),
+            "arch" | "architecture" | "target_cpu" => Some(Var::Arch),
+            "branch" => Some(Var::Branch),
+            "is_increment" => Some(Var::IsIncrement),
+            "is_signed" | "is_sign" => Some(Var::IsSigned),
+            "is_update" => Some(Var::IsUpdate),
+            "installer_format" => Some(Var::InstallerFormat),
+            _ => None,
+        }
+    }
+
+    fn value(&self, vars: &ConditionVars) -> String {
+        match self {
+            Var::Platform => vars.platform.clone(),
+            Var::Arch => vars.arch.clone(),
+            Var::Branch => vars.branch.clone(),
+            Var::IsIncrement => vars.is_increment.to_string(),
+            Var::IsSigned => vars.is_signed.to_string(),
+            Var::IsUpdate => vars.is_update.to_string(),
+            Var::InstallerFormat => vars.installer_format.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Condition {
+    Eq(Var, String),
+    Glob(Var, String),
+    ArgsContain(String),
+    Not(Box<Condition>),
+    And(Box<Condition>, Box<Condition>),
+    Or(Box<Condition>, Box<Condition>),
+}
+
+impl Condition {
+    pub fn parse(input: &str) -> Result<Self> {
+        let tokens = tokenize(input)?;
+        let mut parser = Parser { tokens, pos: 0 };
+        let condition = parser.parse_or()?;
+        if let Some(token) = parser.peek() {
+            return Err(anyhow::anyhow!("条件 '{}' 中存在多余的内容: {}", input, token));
+        }
+        Ok(condition)
+    }
+
+    pub fn eval(&self, vars: &ConditionVars) -> bool {
+        match self {
+            Condition::Eq(var, expected) => var.value(vars).eq_ignore_ascii_case(expected),
+            Condition::Glob(var, pattern) => glob_match(pattern, &var.value(vars)),
+            Condition::ArgsContain(pattern) => vars.custom_args.iter().any(|arg| glob_match(pattern, arg.trim())),
+            Condition::Not(inner) => !inner.eval(vars),
+            Condition::And(lhs, rhs) => lhs.eval(vars) && rhs.eval(vars),
+            Condition::Or(lhs, rhs) => lhs.eval(vars) || rhs.eval(vars),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Word(String),
+    Quoted(String),
+    Op(&'static str),
+    LParen,
+    RParen,
+}
+
+impl std::fmt::Display for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Token::Word(w) => write!(f, "{}", w),
+            Token::Quoted(q) => write!(f, "\"{}\"", q),
+            Token::Op(op) => write!(f, "{}", op),
+            Token::LParen => write!(f, "("),
+            Token::RParen => write!(f, ")"),
+        }
+    }
+}
+
+fn tokenize(input: &str) -> Result<Vec<Token>> {
+    const OPS: [&str; 8] = ["==", "!=", "~=", "!~", "&&", "||", "=", "!"];
+
+    let mut tokens = Vec::new();
+    let mut rest = input;
+    while let Some(c) = rest.chars().next() {
+        if c.is_whitespace() {
+            rest = &rest[c.len_utf8()..];
+        } else if c == '(' {
+            tokens.push(Token::LParen);
+            rest = &rest[1..];
+        } else if c == ')' {
+            tokens.push(Token::RParen);
+            rest = &rest[1..];
+        } else if c == '"' || c == '\'' {
+            let end = rest[1..].find(c)
+                .ok_or_else(|| anyhow::anyhow!("条件 '{}' 中的引号未闭合", input))?;
+            tokens.push(Token::Quoted(rest[1..1 + end].to_string()));
+            rest = &rest[end + 2..];
+        } else if let Some(op) = OPS.iter().find(|op| rest.starts_with(**op)) {
+            tokens.push(Token::Op(op));
+            rest = &rest[op.len()..];
+        } else {
+            let len = rest
+                .find(|ch: char| ch.is_whitespace() || "()\"'=!~&|".contains(ch))
+                .unwrap_or(rest.len());
+            if len == 0 {
+                return Err(anyhow::anyhow!("条件 '{}' 中存在无法识别的字符: {}", input, c));
+            }
+            tokens.push(Token::Word(rest[..len].to_string()));
+            rest = &rest[len..];
+        }
+    }
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    /// 匹配关键字（如 `and`）或其符号形式（如 `&&`）
+    fn eat_keyword(&mut self, keyword: &str, op: Option<&str>) -> bool {
+        let matched = match self.peek() {
+            Some(Token::Word(w)) => w.eq_ignore_ascii_case(keyword),
+            Some(Token::Op(o)) => Some(*o) == op,
+            _ => false,
+        };
+        if matched {
+            self.pos += 1;
+        }
+        matched
+    }
+
+    fn parse_or(&mut self) -> Result<Condition> {
+        let mut lhs = self.parse_and()?;
+        while self.eat_keyword("or", Some("||")) {
+            let rhs = self.parse_and()?;
+            lhs = Condition::Or(Box::new(lhs), Box::new(rhs));
+        }
+        Ok(lhs)
+    }
+
+    fn parse_and(&mut self) -> Result<Condition> {
+        let mut lhs = self.parse_unary()?;
+        while self.eat_keyword("and", Some("&&")) {
+            let rhs = self.parse_unary()?;
+            lhs = Condition::And(Box::new(lhs), Box::new(rhs));
+        }
+        Ok(lhs)
+    }
+
+    fn parse_unary(&mut self) -> Result<Condition> {
+        if self.eat_keyword("not", Some("!")) {
+            return Ok(Condition::Not(Box::new(self.parse_unary()?)));
+        }
+        if self.peek() == Some(&Token::LParen) {
+            self.pos += 1;
+            let inner = self.parse_or()?;
+            if self.next() != Some(Token::RParen) {
+                return Err(anyhow::anyhow!("缺少右括号"));
+            }
+            return Ok(inner);
+        }
+        self.parse_predicate()
+    }
+
+    fn parse_predicate(&mut self) -> Result<Condition> {
+        let name = match self.next() {
+            Some(Token::Word(w)) => w,
+            Some(other) => return Err(anyhow::anyhow!("期望变量名，实际为 {}", other)),
+            None => return Err(anyhow::anyhow!("条件表达式不完整")),
+        };
+
+        if name == "custom_args" {
+            if !self.eat_keyword("contains", None) {
+                return Err(anyhow::anyhow!("custom_args 只支持 contains 判断"));
+            }
+            return Ok(Condition::ArgsContain(self.parse_value()?));
+        }
+
+        let var = Var::parse(&name).ok_or_else(|| {
+            anyhow::anyhow!(
+                "未知的条件变量 '{}'，可选: platform, arch, branch, is_increment, is_signed, is_update, installer_format, custom_args",
+                name
+            )
+        })?;
+
+        let condition = match self.next() {
+            Some(Token::Op("=")) | Some(Token::Op("==")) => Condition::Eq(var, self.parse_value()?),
+            Some(Token::Op("!=")) => Condition::Not(Box::new(Condition::Eq(var, self.parse_value()?))),
+            Some(Token::Op("~=")) => Condition::Glob(var, self.parse_value()?),
+            Some(Token::Op("!~")) => Condition::Not(Box::new(Condition::Glob(var, self.parse_value()?))),
+            Some(other) => return Err(anyhow::anyhow!("变量 '{}' 后期望比较运算符，实际为 {}", name, other)),
+            None => return Err(anyhow::anyhow!("变量 '{}' 后缺少比较运算符", name)),
+        };
+        Ok(condition)
+    }
+
+    fn parse_value(&mut self) -> Result<String> {
+        match self.next() {
+            Some(Token::Word(w)) | Some(Token::Quoted(w)) => Ok(w),
+            Some(other) => Err(anyhow::anyhow!("期望值，实际为 {}", other)),
+            None => Err(anyhow::anyhow!("条件表达式缺少比较值")),
+        }
+    }
+}
+
+/// 简单 glob 匹配：`*` 匹配任意长度，`?` 匹配单个字符
+fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    let (mut p, mut t) = (0, 0);
+    let mut star: Option<(usize, usize)> = None;
+
+    while t < text.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == text[t]) {
+            p += 1;
+            t += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            star = Some((p, t));
+            p += 1;
+        } else if let Some((star_p, star_t)) = star {
+            p = star_p + 1;
+            t = star_t + 1;
+            star = Some((star_p, star_t + 1));
+        } else {
+            return false;
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vars() -> ConditionVars {
+        ConditionVars {
+            platform: "linux".to_string(),
+            arch: "arm64".to_string(),
+            branch: "release/140".to_string(),
+            is_increment: true,
+            is_signed: false,
+            is_update: false,
+            installer_format: String::new(),
+            custom_args: vec!["is_debug=false".to_string(), "symbol_level=2".to_string()],
+        }
+    }
+
+    fn eval(input: &str) -> bool {
+        Condition::parse(input).unwrap().eval(&vars())
+    }
+
+    #[test]
+    fn legacy_equality_still_works() {
+        assert!(eval("is_update=false"));
+        assert!(!eval("is_update=true"));
+        assert!(eval("target_os=linux"));
+    }
+
+    #[test]
+    fn boolean_operators_and_precedence() {
+        assert!(eval("platform=linux and arch=arm64"));
+        assert!(eval("platform=macos or arch=arm64 and is_increment=true"));
+        assert!(!eval("(platform=macos or arch=arm64) and is_signed=true"));
+        assert!(eval("not platform=windows && !is_signed=true"));
+        assert!(eval("arch != x64"));
+    }
+
+    #[test]
+    fn glob_and_custom_args() {
+        assert!(eval("branch ~= \"release/*\""));
+        assert!(!eval("branch !~ release/1??"));
+        assert!(eval("custom_args contains \"symbol_level=*\""));
+        assert!(!eval("custom_args contains \"is_asan=true\""));
+    }
+
+    #[test]
+    fn rejects_invalid_expressions() {
+        assert!(Condition::parse("unknown_var=1").is_err());
+        assert!(Condition::parse("platform").is_err());
+        assert!(Condition::parse("platform=linux and").is_err());
+        assert!(Condition::parse("(platform=linux").is_err());
+        assert!(Condition::parse("custom_args = x").is_err());
+        assert!(Condition::parse("branch ~= \"release/*").is_err());
+    }
+
+    #[test]
+    fn glob_matching() {
+        assert!(glob_match("*", ""));
+        assert!(glob_match("a*c", "abbbc"));
+        assert!(glob_match("a?c", "abc"));
+        assert!(!glob_match("a?c", "ac"));
+        assert!(glob_match("*/140", "release/140"));
+    }
+}
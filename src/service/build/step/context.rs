@@ -0,0 +1,80 @@
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use crate::api::ws::WsManager;
+use crate::config::AppConfig;
+use crate::model::build::BuildRequest;
+use crate::repository::task::TaskRepository;
+use crate::service::backup::BackupManager;
+use crate::service::build::{Compiler, InstallerBuilder, ProjectBuilder};
+
+/// 单次构建中所有步骤共享的执行上下文
+pub struct StepContext {
+    #[allow(dead_code)]
+    pub config: Arc<AppConfig>,
+    pub request: BuildRequest,
+    pub task_id: i64,
+    pub task_repo: TaskRepository,
+    pub src_path: PathBuf,
+    pub out_dir: String,
+    pub builder: ProjectBuilder,
+    pub compiler: Compiler,
+    pub installer: InstallerBuilder,
+    #[allow(dead_code)]
+    pub backup_manager: BackupManager,
+    pub ws_manager: Option<WsManager>,
+    pub cancelled_flag: Option<Arc<AtomicBool>>,
+    // 由 git get_commit_id 步骤写入，构建完成时回写到任务
+    commit_id: Mutex<String>,
+}
+
+impl StepContext {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        config: Arc<AppConfig>,
+        request: BuildRequest,
+        task_id: i64,
+        task_repo: TaskRepository,
+        src_path: PathBuf,
+        out_dir: String,
+        builder: ProjectBuilder,
+        compiler: Compiler,
+        installer: InstallerBuilder,
+        backup_manager: BackupManager,
+        ws_manager: Option<WsManager>,
+        cancelled_flag: Option<Arc<AtomicBool>>,
+    ) -> Self {
+        Self {
+            config,
+            request,
+            task_id,
+            task_repo,
+            src_path,
+            out_dir,
+            builder,
+            compiler,
+            installer,
+            backup_manager,
+            ws_manager,
+            cancelled_flag,
+            commit_id: Mutex::new(String::new()),
+        }
+    }
+
+    pub fn commit_id(&self) -> String {
+        self.commit_id.lock().map(|id| id.clone()).unwrap_or_default()
+    }
+
+    pub fn set_commit_id(&self, commit_id: &str) {
+        if let Ok(mut id) = self.commit_id.lock() {
+            *id = commit_id.to_string();
+        }
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled_flag
+            .as_ref()
+            .map(|flag| flag.load(Ordering::Relaxed))
+            .unwrap_or(false)
+    }
+}
@@ -0,0 +1,205 @@
+use anyhow::Result;
+use futures_util::future::BoxFuture;
+use futures_util::FutureExt;
+use crate::config::BuildStep;
+use crate::model::state::TaskState;
+use crate::model::step::StepKind;
+use crate::service::build::step::context::StepContext;
+use crate::service::build::step::registry::{BuildStepExecutor, StepOutcome};
+use crate::util::git;
+
+/// git 步骤：target 为 update（更新代码）或 get_commit_id（记录当前 commit）
+pub struct GitStep;
+
+impl BuildStepExecutor for GitStep {
+    fn kind(&self) -> StepKind {
+        StepKind::Git
+    }
+
+    fn validate(&self, step: &BuildStep) -> Result<()> {
+        match step.target.as_deref() {
+            Some("update") | Some("get_commit_id") => Ok(()),
+            other => Err(anyhow::anyhow!(
+                "步骤 '{}' 的 git 操作 {:?} 无效，可选: update, get_commit_id",
+                step.name,
+                other
+            )),
+        }
+    }
+
+    fn execute<'a>(&'a self, ctx: &'a StepContext, step: &'a BuildStep) -> BoxFuture<'a, Result<StepOutcome>> {
+        async move {
+            match step.target.as_deref() {
+                Some("update") => {
+                    git::update_code(
+                        &ctx.src_path,
+                        &ctx.request.branch,
+                        ctx.request.commit_id.as_deref(),
+                    ).await?;
+                },
+                Some("get_commit_id") => {
+                    let commit_id = git::get_commit_id(&ctx.src_path).await?;
+                    ctx.set_commit_id(&commit_id);
+                    tracing::info!("✅ Commit ID: {}\n", commit_id);
+
+                    // 在第一次获取 commit_id 时，立即更新父任务和所有子任务的 commit_id
+                    if let Err(e) = ctx.task_repo.update_family_commit_id(ctx.task_id, &commit_id).await {
+                        tracing::warn!("⚠️  更新父子任务 commit_id 失败: {}", e);
+                    }
+
+                    // 更新当前任务的状态
+                    if let Some(state) = step.state.as_deref().and_then(TaskState::from_str) {
+                        ctx.task_repo.update_state(ctx.task_id, state, Some(&commit_id)).await?;
+                    }
+                },
+                other => return Err(anyhow::anyhow!("未知的 git 操作: {:?}", other)),
+            }
+            Ok(StepOutcome::Done)
+        }.boxed()
+    }
+}
+
+/// clean 步骤：按 is_increment 决定是否删除输出目录，并清理配置路径
+pub struct CleanStep;
+
+impl BuildStepExecutor for CleanStep {
+    fn kind(&self) -> StepKind {
+        StepKind::Clean
+    }
+
+    fn execute<'a>(&'a self, ctx: &'a StepContext, _step: &'a BuildStep) -> BoxFuture<'a, Result<StepOutcome>> {
+        async move {
+            ctx.builder.clean(&ctx.src_path, &ctx.out_dir, ctx.request.is_increment).await?;
+            Ok(StepOutcome::Done)
+        }.boxed()
+    }
+}
+
+/// gn_gen 步骤：生成 ninja 工程
+pub struct GnGenStep;
+
+impl BuildStepExecutor for GnGenStep {
+    fn kind(&self) -> StepKind {
+        StepKind::GnGen
+    }
+
+    fn execute<'a>(&'a self, ctx: &'a StepContext, _step: &'a BuildStep) -> BoxFuture<'a, Result<StepOutcome>> {
+        async move {
+            ctx.builder.generate(&ctx.src_path, &ctx.out_dir, &ctx.request).await?;
+            Ok(StepOutcome::Done)
+        }.boxed()
+    }
+}
+
+/// ninja 步骤：编译 target 指定的目标
+pub struct NinjaStep;
+
+impl BuildStepExecutor for NinjaStep {
+    fn kind(&self) -> StepKind {
+        StepKind::Ninja
+    }
+
+    fn validate(&self, step: &BuildStep) -> Result<()> {
+        match step.target.as_deref() {
+            Some(target) if !target.trim().is_empty() => Ok(()),
+            _ => Err(anyhow::anyhow!("ninja 步骤 '{}' 缺少 target", step.name)),
+        }
+    }
+
+    fn execute<'a>(&'a self, ctx: &'a StepContext, step: &'a BuildStep) -> BoxFuture<'a, Result<StepOutcome>> {
+        async move {
+            let target = step.target.as_deref().unwrap_or_default();
+            ctx.compiler.build_targets(
+                &ctx.src_path,
+                &ctx.out_dir,
+                &[target],
+                &step.name,
+                Some(ctx.task_id),
+                Some(&ctx.task_repo),
+                ctx.ws_manager.as_ref(),
+                ctx.cancelled_flag.clone(),
+            ).await?;
+            Ok(StepOutcome::Done)
+        }.boxed()
+    }
+}
+
+/// installer 步骤：生成安装包
+pub struct InstallerStep;
+
+impl BuildStepExecutor for InstallerStep {
+    fn kind(&self) -> StepKind {
+        StepKind::Installer
+    }
+
+    fn execute<'a>(&'a self, ctx: &'a StepContext, _step: &'a BuildStep) -> BoxFuture<'a, Result<StepOutcome>> {
+        async move {
+            // macOS 的子任务跳过 installer，等待父任务的组合步骤
+            let task = ctx.task_repo.find_by_id(ctx.task_id).await?;
+            if task.parent_id.is_some() && ctx.request.platform == "macos" {
+                return Ok(StepOutcome::Skipped("macOS 组合任务将在父任务中执行".to_string()));
+            }
+
+            ctx.installer.build_installer(
+                &ctx.src_path,
+                &ctx.out_dir,
+                ctx.request.installer_format.as_deref(),
+            ).await?;
+            Ok(StepOutcome::Done)
+        }.boxed()
+    }
+}
+
+/// combine 步骤：仅用于父任务，组合多个架构的 app 并生成 universal pkg
+pub struct CombineStep;
+
+impl BuildStepExecutor for CombineStep {
+    fn kind(&self) -> StepKind {
+        StepKind::Combine
+    }
+
+    fn execute<'a>(&'a self, ctx: &'a StepContext, _step: &'a BuildStep) -> BoxFuture<'a, Result<StepOutcome>> {
+        async move {
+            if ctx.request.platform != "macos" {
+                return Err(anyhow::anyhow!("组合任务仅支持 macOS"));
+            }
+
+            if ctx.request.architectures.len() < 2 {
+                return Err(anyhow::anyhow!("组合任务需要至少2个架构"));
+            }
+
+            let task = ctx.task_repo.find_by_id(ctx.task_id).await?;
+            if task.parent_id.is_some() {
+                return Err(anyhow::anyhow!("组合步骤只能在父任务中执行"));
+            }
+
+            let children = ctx.task_repo.get_child_tasks(ctx.task_id).await?;
+            if children.len() < 2 {
+                return Err(anyhow::anyhow!("组合任务需要至少2个子任务"));
+            }
+
+            if !ctx.task_repo.all_children_completed_chrome(ctx.task_id).await? {
+                return Err(anyhow::anyhow!("等待所有子任务完成 build chrome"));
+            }
+
+            ctx.installer.combine_universal_pkg(&ctx.src_path, &ctx.request.architectures).await?;
+            Ok(StepOutcome::Done)
+        }.boxed()
+    }
+}
+
+/// backup 步骤：备份构建产物
+pub struct BackupStep;
+
+impl BuildStepExecutor for BackupStep {
+    fn kind(&self) -> StepKind {
+        StepKind::Backup
+    }
+
+    fn execute<'a>(&'a self, _ctx: &'a StepContext, _step: &'a BuildStep) -> BoxFuture<'a, Result<StepOutcome>> {
+        async move {
+            // TODO: 实现备份逻辑
+            Ok(StepOutcome::Skipped("备份功能待实现".to_string()))
+        }.boxed()
+    }
+}
@@ -0,0 +1,8 @@
+pub mod condition;
+pub mod context;
+pub mod policy;
+pub mod registry;
+pub mod executors;
+
+pub use context::*;
+pub use registry::*;
@@ -0,0 +1,158 @@
+//! 步骤的超时与重试策略（对应 `BuildStep` 的 timeout_secs / retries /
+//! retry_backoff_secs / retry_on）。
+//!
+//! 超时通过丢弃执行中的 future 实现：各步骤启动的外部命令都运行在独立
+//! 进程组中（见 `util::process`），future 被丢弃时整个进程树会被终止。
+
+use std::time::Duration;
+use anyhow::Result;
+use regex::Regex;
+use crate::config::{BuildStep, RetryOn};
+use crate::service::build::step::context::StepContext;
+use crate::service::build::step::registry::{BuildStepExecutor, StepOutcome};
+
+/// 未配置 retry_backoff_secs 时的重试间隔
+const DEFAULT_RETRY_BACKOFF_SECS: u64 = 30;
+
+/// 步骤执行超时
+#[derive(Debug)]
+pub struct StepTimedOut {
+    pub step: String,
+    pub timeout: Duration,
+}
+
+impl std::fmt::Display for StepTimedOut {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "步骤 '{}' 执行超时（{} 秒），已终止进程", self.step, self.timeout.as_secs())
+    }
+}
+
+impl std::error::Error for StepTimedOut {}
+
+enum RetryMatcher {
+    ExitCode(i32),
+    Pattern(Regex),
+}
+
+pub struct StepPolicy {
+    timeout: Option<Duration>,
+    retries: u32,
+    backoff: Duration,
+    retry_on: Vec<RetryMatcher>,
+}
+
+impl StepPolicy {
+    /// 从步骤配置构建策略，retry_on 中的正则无效时返回错误（配置加载时即可发现）
+    pub fn from_step(step: &BuildStep) -> Result<Self> {
+        if step.timeout_secs == Some(0) {
+            return Err(anyhow::anyhow!("步骤 '{}' 的 timeout_secs 必须大于 0", step.name));
+        }
+
+        let retry_on = step.retry_on.iter()
+            .map(|item| match item {
+                RetryOn::ExitCode(code) => Ok(RetryMatcher::ExitCode(*code)),
+                RetryOn::Pattern(pattern) => Regex::new(pattern)
+                    .map(RetryMatcher::Pattern)
+                    .map_err(|e| anyhow::anyhow!("步骤 '{}' 的 retry_on 正则 '{}' 无效: {}", step.name, pattern, e)),
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self {
+            timeout: step.timeout_secs.map(Duration::from_secs),
+            retries: step.retries,
+            backoff: Duration::from_secs(step.retry_backoff_secs.unwrap_or(DEFAULT_RETRY_BACKOFF_SECS)),
+            retry_on,
+        })
+    }
+
+    /// 按策略执行步骤：每次执行受 timeout 限制，失败且满足 retry_on 时按退避间隔重试
+    pub async fn run(
+        &self,
+        executor: &dyn BuildStepExecutor,
+        ctx: &StepContext,
+        step: &BuildStep,
+    ) -> Result<StepOutcome> {
+        let mut attempt = 0;
+        loop {
+            let result = match self.timeout {
+                Some(timeout) => match tokio::time::timeout(timeout, executor.execute(ctx, step)).await {
+                    Ok(result) => result,
+                    Err(_) => Err(anyhow::Error::new(StepTimedOut {
+                        step: step.name.clone(),
+                        timeout,
+                    })),
+                },
+                None => executor.execute(ctx, step).await,
+            };
+
+            let err = match result {
+                Ok(outcome) => return Ok(outcome),
+                Err(err) => err,
+            };
+
+            if attempt >= self.retries || ctx.is_cancelled() || !self.should_retry(&err) {
+                return Err(err);
+            }
+
+            attempt += 1;
+            let delay = self.backoff * 2u32.saturating_pow(attempt - 1);
+            let log_line = format!(
+                "[{}] 执行失败，{} 秒后第 {}/{} 次重试: {}",
+                step.name,
+                delay.as_secs(),
+                attempt,
+                self.retries,
+                err
+            );
+            tracing::warn!("🔁 {}", log_line);
+            let _ = ctx.task_repo.append_build_log(ctx.task_id, &log_line).await;
+            if let Some(ws) = &ctx.ws_manager {
+                ws.broadcast_log(ctx.task_id, log_line, false);
+            }
+
+            tokio::time::sleep(delay).await;
+        }
+    }
+
+    fn should_retry(&self, err: &anyhow::Error) -> bool {
+        if self.retry_on.is_empty() {
+            return true;
+        }
+
+        let message = format!("{:#}", err);
+        let exit_code = exit_code_of(err);
+        self.retry_on.iter().any(|matcher| match matcher {
+            RetryMatcher::ExitCode(code) => exit_code == Some(*code),
+            RetryMatcher::Pattern(regex) => regex.is_match(&message),
+        })
+    }
+}
+
+/// 从命令执行错误（"... failed with exit code N: ..."）中提取退出码
+pub fn exit_code_of(err: &anyhow::Error) -> Option<i32> {
+    let message = err.to_string();
+    let rest = &message[message.find("exit code ")? + "exit code ".len()..];
+    let end = rest
+        .char_indices()
+        .find(|(i, c)| !(c.is_ascii_digit() || (*i == 0 && *c == '-')))
+        .map(|(i, _)| i)
+        .unwrap_or(rest.len());
+    rest[..end].parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extracts_exit_code_from_command_errors() {
+        let err = anyhow::anyhow!("gn gen failed with exit code 1: ERROR at //BUILD.gn");
+        assert_eq!(exit_code_of(&err), Some(1));
+
+        let err = anyhow::anyhow!("编译 chrome failed with exit code -1: ");
+        assert_eq!(exit_code_of(&err), Some(-1));
+
+        let err = anyhow::anyhow!("Task cancelled");
+        assert_eq!(exit_code_of(&err), None);
+    }
+}
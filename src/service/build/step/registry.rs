@@ -0,0 +1,129 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use anyhow::Result;
+use futures_util::future::BoxFuture;
+use crate::config::{AppConfig, BuildStep};
+use crate::model::step::StepKind;
+use crate::service::build::step::condition::Condition;
+use crate::service::build::step::context::StepContext;
+use crate::service::build::step::policy::StepPolicy;
+use crate::service::build::step::executors;
+
+/// 步骤执行结果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StepOutcome {
+    /// 步骤正常完成
+    Done,
+    /// 步骤在执行期间判断无需执行（附带原因）
+    Skipped(String),
+}
+
+/// 构建步骤执行器
+///
+/// 每种 `StepKind` 对应一个执行器，注册到 `StepRegistry` 后即可在
+/// `build_steps` 中使用，`do_build` 本身不需要感知具体的步骤类型。
+pub trait BuildStepExecutor: Send + Sync {
+    fn kind(&self) -> StepKind;
+
+    /// 配置加载时校验步骤参数（如 ninja 必须指定 target）
+    fn validate(&self, _step: &BuildStep) -> Result<()> {
+        Ok(())
+    }
+
+    fn execute<'a>(
+        &'a self,
+        ctx: &'a StepContext,
+        step: &'a BuildStep,
+    ) -> BoxFuture<'a, Result<StepOutcome>>;
+}
+
+#[derive(Clone)]
+pub struct StepRegistry {
+    executors: HashMap<StepKind, Arc<dyn BuildStepExecutor>>,
+}
+
+impl StepRegistry {
+    pub fn new() -> Self {
+        Self {
+            executors: HashMap::new(),
+        }
+    }
+
+    /// 注册了全部内置步骤类型的执行器
+    pub fn builtin() -> Self {
+        let mut registry = Self::new();
+        registry.register(executors::GitStep);
+        registry.register(executors::CleanStep);
+        registry.register(executors::GnGenStep);
+        registry.register(executors::NinjaStep);
+        registry.register(executors::InstallerStep);
+        registry.register(executors::CombineStep);
+        registry.register(executors::BackupStep);
+        registry
+    }
+
+    pub fn register<E: BuildStepExecutor + 'static>(&mut self, executor: E) {
+        self.executors.insert(executor.kind(), Arc::new(executor));
+    }
+
+    /// 根据步骤配置查找执行器，未知类型或未注册的类型返回错误
+    pub fn resolve(&self, step: &BuildStep) -> Result<Arc<dyn BuildStepExecutor>> {
+        let kind = StepKind::from_str(&step.step_type).ok_or_else(|| {
+            anyhow::anyhow!(
+                "步骤 '{}' 的类型 '{}' 无效，可选类型: {}",
+                step.name,
+                step.step_type,
+                self.kind_names().join(", ")
+            )
+        })?;
+
+        self.executors.get(&kind).cloned().ok_or_else(|| {
+            anyhow::anyhow!("步骤 '{}' 的类型 '{}' 没有注册执行器", step.name, kind)
+        })
+    }
+
+    pub fn validate_step(&self, step: &BuildStep) -> Result<()> {
+        self.resolve(step)?.validate(step)?;
+        if let Some(skip_if) = &step.skip_if {
+            Condition::parse(skip_if)
+                .map_err(|e| anyhow::anyhow!("步骤 '{}' 的 skip_if 无效: {}", step.name, e))?;
+        }
+        StepPolicy::from_step(step)?;
+        Ok(())
+    }
+
+    /// 校验配置中所有平台、所有架构的构建步骤，汇总全部错误后一次性返回
+    pub fn validate_config(&self, config: &AppConfig) -> Result<()> {
+        let errors: Vec<String> = config
+            .all_build_steps()
+            .into_iter()
+            .flat_map(|(platform, arch, steps)| {
+                steps.iter().filter_map(move |step| {
+                    self.validate_step(step)
+                        .err()
+                        .map(|e| format!("build_steps.{}.{}: {}", platform, arch, e))
+                })
+            })
+            .collect();
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(anyhow::anyhow!("构建步骤配置无效:\n  {}", errors.join("\n  ")))
+        }
+    }
+
+    fn kind_names(&self) -> Vec<&'static str> {
+        StepKind::ALL
+            .iter()
+            .filter(|kind| self.executors.contains_key(kind))
+            .map(|kind| kind.as_str())
+            .collect()
+    }
+}
+
+impl Default for StepRegistry {
+    fn default() -> Self {
+        Self::builtin()
+    }
+}
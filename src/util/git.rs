@@ -1,6 +1,7 @@
 use std::path::Path;
 use anyhow::{Context, Result};
-use std::process::Command;
+use tokio::process::Command;
+use crate::util::process;
 use crate::util::retry::retry_async;
 
 pub async fn update_code(
@@ -12,10 +13,9 @@ pub async fn update_code(
     tracing::info!("📋 执行命令: git stash");
     tracing::info!("📁 工作目录: {}", src_path.display());
     let start_time = std::time::Instant::now();
-    let stash_output = Command::new("git")
+    let stash_output = process::output(Command::new("git")
         .arg("stash")
-        .current_dir(src_path)
-        .output()
+        .current_dir(src_path)).await
         .context("Failed to stash changes")?;
     let duration = start_time.elapsed();
     let exit_code = stash_output.status.code().unwrap_or(-1);
@@ -33,11 +33,10 @@ pub async fn update_code(
         tracing::info!("📋 执行命令: git checkout {}", commit);
         tracing::info!("📁 工作目录: {}", src_path.display());
         let start_time = std::time::Instant::now();
-        let checkout_output = Command::new("git")
+        let checkout_output = process::output(Command::new("git")
             .arg("checkout")
             .arg(commit)
-            .current_dir(src_path)
-            .output()
+            .current_dir(src_path)).await
             .context("Failed to checkout commit")?;
         let duration = start_time.elapsed();
         let exit_code = checkout_output.status.code().unwrap_or(-1);
@@ -64,11 +63,10 @@ pub async fn update_code(
     tracing::info!("📋 执行命令: git checkout {}", branch);
     tracing::info!("📁 工作目录: {}", src_path.display());
     let start_time = std::time::Instant::now();
-    let checkout_output = Command::new("git")
+    let checkout_output = process::output(Command::new("git")
         .arg("checkout")
         .arg(branch)
-        .current_dir(src_path)
-        .output()
+        .current_dir(src_path)).await
         .context("Failed to checkout branch")?;
     let duration = start_time.elapsed();
     let exit_code = checkout_output.status.code().unwrap_or(-1);
@@ -95,10 +93,9 @@ pub async fn update_code(
     tracing::info!("📁 工作目录: {}", src_path.display());
     let pull_start = std::time::Instant::now();
     retry_async(|| async {
-        let output = Command::new("git")
+        let output = process::output(Command::new("git")
             .arg("pull")
-            .current_dir(src_path)
-            .output()?;
+            .current_dir(src_path)).await?;
         
         let exit_code = output.status.code().unwrap_or(-1);
         if !output.stdout.is_empty() {
@@ -130,10 +127,9 @@ pub async fn get_commit_id(src_path: &Path) -> Result<String> {
     tracing::info!("📋 执行命令: git rev-parse HEAD");
     tracing::info!("📁 工作目录: {}", src_path.display());
     
-    let output = Command::new("git")
+    let output = process::output(Command::new("git")
         .args(&["rev-parse", "HEAD"])
-        .current_dir(src_path)
-        .output()
+        .current_dir(src_path)).await
         .context("Failed to get commit id")?;
     
     let exit_code = output.status.code().unwrap_or(-1);
@@ -159,10 +155,9 @@ pub async fn get_branch_list(src_path: &Path) -> Result<Vec<String>> {
     tracing::info!("📋 执行命令: git branch -a");
     tracing::info!("📁 工作目录: {}", src_path.display());
     
-    let output = Command::new("git")
+    let output = process::output(Command::new("git")
         .args(&["branch", "-a"])
-        .current_dir(src_path)
-        .output()
+        .current_dir(src_path)).await
         .context("Failed to get branch list")?;
     
     let exit_code = output.status.code().unwrap_or(-1);
@@ -4,4 +4,5 @@ pub mod path;
 pub mod time;
 pub mod retry;
 pub mod progress;
+pub mod process;
 
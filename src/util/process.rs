@@ -0,0 +1,118 @@
+use std::io;
+use std::ops::{Deref, DerefMut};
+use std::process::{ExitStatus, Output, Stdio};
+use tokio::io::AsyncReadExt;
+use tokio::process::{Child, Command};
+
+/// 在独立的进程组中运行的子进程
+///
+/// 构建命令都是通过 `sh -c` / `cmd /c` 启动的，真正干活的 ninja、gn、git
+/// 是 shell 的子孙进程，只 kill shell 本身并不能让它们退出。这里让子进程
+/// 成为新进程组的组长，超时或取消导致 future 被丢弃时，在 drop 中杀掉整个
+/// 进程树；正常 `wait` 结束后不再处理（避免误杀 sccache 这类常驻进程）。
+pub struct GroupChild {
+    child: Child,
+    pid: Option<u32>,
+    armed: bool,
+}
+
+/// 以独立进程组启动命令
+pub fn spawn_group(command: &mut Command) -> io::Result<GroupChild> {
+    #[cfg(unix)]
+    command.process_group(0);
+    command.kill_on_drop(true);
+
+    let child = command.spawn()?;
+    let pid = child.id();
+    Ok(GroupChild { child, pid, armed: true })
+}
+
+/// 与 `Command::output` 相同，但进程在独立进程组中运行，future 被丢弃时会杀掉整个进程树
+pub async fn output(command: &mut Command) -> io::Result<Output> {
+    command
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    spawn_group(command)?.wait_with_output().await
+}
+
+impl GroupChild {
+    /// 等待进程退出，退出后不再在 drop 时杀进程组
+    pub async fn wait(&mut self) -> io::Result<ExitStatus> {
+        let status = self.child.wait().await?;
+        self.armed = false;
+        Ok(status)
+    }
+
+    pub async fn wait_with_output(mut self) -> io::Result<Output> {
+        async fn read_pipe<R: tokio::io::AsyncRead + Unpin>(pipe: Option<R>) -> io::Result<Vec<u8>> {
+            let mut buf = Vec::new();
+            if let Some(mut pipe) = pipe {
+                pipe.read_to_end(&mut buf).await?;
+            }
+            Ok(buf)
+        }
+
+        let stdout = self.child.stdout.take();
+        let stderr = self.child.stderr.take();
+        let (status, stdout, stderr) = tokio::try_join!(
+            self.child.wait(),
+            read_pipe(stdout),
+            read_pipe(stderr),
+        )?;
+        self.armed = false;
+
+        Ok(Output { status, stdout, stderr })
+    }
+
+    /// 立即杀掉整个进程树
+    pub fn kill_tree(&mut self) {
+        let Some(pid) = self.pid else {
+            return;
+        };
+
+        #[cfg(unix)]
+        let result = std::process::Command::new("kill")
+            .arg("-KILL")
+            .arg(format!("-{}", pid))
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status();
+
+        #[cfg(windows)]
+        let result = std::process::Command::new("taskkill")
+            .args(["/T", "/F", "/PID", &pid.to_string()])
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status();
+
+        match result {
+            Ok(status) if status.success() => tracing::info!("🛑 已终止进程树 (PID: {})", pid),
+            Ok(status) => tracing::debug!("终止进程树 {} 返回 {:?}（进程可能已退出）", pid, status),
+            Err(e) => tracing::warn!("⚠️  终止进程树 {} 失败: {}", pid, e),
+        }
+        self.armed = false;
+    }
+}
+
+impl Deref for GroupChild {
+    type Target = Child;
+
+    fn deref(&self) -> &Child {
+        &self.child
+    }
+}
+
+impl DerefMut for GroupChild {
+    fn deref_mut(&mut self) -> &mut Child {
+        &mut self.child
+    }
+}
+
+impl Drop for GroupChild {
+    fn drop(&mut self) {
+        if self.armed {
+            self.kill_tree();
+        }
+    }
+}